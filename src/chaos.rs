@@ -1,15 +1,316 @@
+use crate::text::Style;
 use crate::{Page, types::Vector2};
 use crossterm::{
     ExecutableCommand, cursor,
-    event::{self, Event, KeyCode, KeyEvent},
-    terminal::{self, enable_raw_mode},
+    event::{self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent},
+    style,
+    terminal::{self},
 };
-use std::io::{self, Write};
+use std::collections::VecDeque;
+use std::io::{self, IsTerminal, Write};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Fallback dimensions used when a backend can't report its size (e.g. output redirected to a
+/// file or pipe).
+const DEFAULT_DIMENSIONS: (u16, u16) = (80, 24);
+
+/// A single on-screen cell: a character paired with the style it should be drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    ch: char,
+    style: Style,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+            style: Style::default(),
+        }
+    }
+}
+
+/// Abstracts the terminal operations [`Chaos`] needs to size itself and write to the screen, so
+/// it can run against a real terminal, an in-memory buffer for tests, or any other sink.
+///
+/// [`Chaos`] defaults to `std::io::Stdout`; swap in [`MemoryBackend`] (or your own `Backend`
+/// implementation) to run it headless, e.g. in tests or a pipeline.
+pub trait Backend: Write {
+    /// Returns the current size of the backend's output area, in columns and rows.
+    fn size(&self) -> io::Result<(u16, u16)>;
+
+    /// Returns the current cursor position, in columns and rows.
+    fn cursor_position(&self) -> io::Result<(u16, u16)>;
+
+    /// Enables raw mode, where input must be handled manually.
+    fn enable_raw_mode(&mut self) -> io::Result<()>;
+
+    /// Disables raw mode, restoring the terminal's normal line-buffered behavior.
+    fn disable_raw_mode(&mut self) -> io::Result<()>;
+
+    /// Moves the cursor to the given column/row.
+    fn goto(&mut self, x: u16, y: u16) -> io::Result<()>;
+
+    /// Shows the cursor.
+    fn show_cursor(&mut self) -> io::Result<()>;
+
+    /// Enters the alternate screen.
+    fn enter_alternate_screen(&mut self) -> io::Result<()>;
+
+    /// Leaves the alternate screen.
+    fn leave_alternate_screen(&mut self) -> io::Result<()>;
+
+    /// Clears the whole screen.
+    fn clear(&mut self) -> io::Result<()>;
+
+    /// Enables bracketed paste reporting.
+    fn enable_bracketed_paste(&mut self) -> io::Result<()>;
+
+    /// Disables bracketed paste reporting.
+    fn disable_bracketed_paste(&mut self) -> io::Result<()>;
+
+    /// Applies `style` to subsequent writes, until [`Backend::reset_style`] is called.
+    fn set_style(&mut self, style: Style) -> io::Result<()>;
+
+    /// Resets any style applied by [`Backend::set_style`].
+    fn reset_style(&mut self) -> io::Result<()>;
+
+    /// Returns whether this backend is connected to a real terminal. [`Chaos`] skips raw mode
+    /// and styling on backends that report `false`, so redirected output stays clean.
+    fn is_tty(&self) -> bool;
+}
+
+impl Backend for io::Stdout {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        terminal::size()
+    }
+
+    fn cursor_position(&self) -> io::Result<(u16, u16)> {
+        cursor::position()
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        terminal::disable_raw_mode()
+    }
+
+    fn goto(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.execute(cursor::MoveTo(x, y))?;
+        Ok(())
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.execute(cursor::Show)?;
+        Ok(())
+    }
+
+    fn enter_alternate_screen(&mut self) -> io::Result<()> {
+        self.execute(terminal::EnterAlternateScreen)?;
+        Ok(())
+    }
+
+    fn leave_alternate_screen(&mut self) -> io::Result<()> {
+        self.execute(terminal::LeaveAlternateScreen)?;
+        Ok(())
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.execute(terminal::Clear(terminal::ClearType::All))?;
+        Ok(())
+    }
+
+    fn enable_bracketed_paste(&mut self) -> io::Result<()> {
+        self.execute(EnableBracketedPaste)?;
+        Ok(())
+    }
+
+    fn disable_bracketed_paste(&mut self) -> io::Result<()> {
+        self.execute(DisableBracketedPaste)?;
+        Ok(())
+    }
+
+    fn set_style(&mut self, style: Style) -> io::Result<()> {
+        if let Some(foreground) = style.foreground {
+            self.execute(style::SetForegroundColor(foreground))?;
+        }
+        if let Some(background) = style.background {
+            self.execute(style::SetBackgroundColor(background))?;
+        }
+        if style.bold {
+            self.execute(style::SetAttribute(style::Attribute::Bold))?;
+        }
+        if style.underline {
+            self.execute(style::SetAttribute(style::Attribute::Underlined))?;
+        }
+        Ok(())
+    }
+
+    fn reset_style(&mut self) -> io::Result<()> {
+        self.execute(style::ResetColor)?;
+        self.execute(style::SetAttribute(style::Attribute::Reset))?;
+        Ok(())
+    }
+
+    fn is_tty(&self) -> bool {
+        self.is_terminal()
+    }
+}
+
+/// An in-memory [`Backend`], useful for tests and non-interactive pipelines.
+///
+/// It buffers everything written to it instead of touching a real terminal, reports a fixed
+/// size, and never claims to be a tty, so [`Chaos`] automatically skips raw mode and styling
+/// when using it.
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    output: Vec<u8>,
+    size: (u16, u16),
+    cursor: (u16, u16),
+}
+
+impl MemoryBackend {
+    /// Creates a blank in-memory backend with the given column/row size.
+    pub fn new(columns: u16, rows: u16) -> Self {
+        Self {
+            output: Vec::new(),
+            size: (columns, rows),
+            cursor: (0, 0),
+        }
+    }
+
+    /// Returns everything written to the backend since it was created or last cleared.
+    pub fn output(&self) -> String {
+        String::from_utf8_lossy(&self.output).into_owned()
+    }
+}
+
+impl Write for MemoryBackend {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok(self.size)
+    }
+
+    fn cursor_position(&self) -> io::Result<(u16, u16)> {
+        Ok(self.cursor)
+    }
+
+    fn enable_raw_mode(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn disable_raw_mode(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn goto(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.cursor = (x, y);
+        Ok(())
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn enter_alternate_screen(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn leave_alternate_screen(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.output.clear();
+        Ok(())
+    }
+
+    fn enable_bracketed_paste(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn disable_bracketed_paste(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn set_style(&mut self, _style: Style) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn reset_style(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn is_tty(&self) -> bool {
+        false
+    }
+}
+
+/// Abstracts where [`Chaos::get_input`] gets its stream of terminal events from, so the
+/// interactive input loop can run against a real terminal or a scripted sequence of events in
+/// tests.
+///
+/// [`Chaos`] defaults to [`CrosstermEvents`]; swap in [`ScriptedEvents`] (or your own
+/// `EventSource` implementation) to drive `get_input` headlessly.
+pub trait EventSource {
+    /// Blocks until the next event is available and returns it.
+    fn read(&mut self) -> io::Result<Event>;
+}
+
+/// Reads events from the real terminal via crossterm.
+#[derive(Debug, Default)]
+pub struct CrosstermEvents;
+
+impl EventSource for CrosstermEvents {
+    fn read(&mut self) -> io::Result<Event> {
+        event::read()
+    }
+}
+
+/// A scripted [`EventSource`] that replays a fixed sequence of events in order, useful for
+/// testing [`Chaos::get_input`] without a real terminal.
+#[derive(Debug, Default)]
+pub struct ScriptedEvents {
+    events: VecDeque<Event>,
+}
+
+impl ScriptedEvents {
+    /// Creates a scripted event source that replays `events` in order.
+    pub fn new(events: impl IntoIterator<Item = Event>) -> Self {
+        Self {
+            events: events.into_iter().collect(),
+        }
+    }
+}
+
+impl EventSource for ScriptedEvents {
+    fn read(&mut self) -> io::Result<Event> {
+        self.events
+            .pop_front()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "no more scripted events"))
+    }
+}
 
 /// The primary struct of chaos-engine.
 ///
 /// This struct must be instantiated once to start using chaos-engine and its features.
 ///
+/// It is generic over its output [`Backend`], defaulting to `std::io::Stdout`, and its
+/// [`EventSource`], defaulting to [`CrosstermEvents`]. When the backend reports it isn't a tty,
+/// raw mode, bracketed paste, and styling are all skipped automatically, so redirected output
+/// and in-memory backends like [`MemoryBackend`] just get plain text.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -20,48 +321,100 @@ use std::io::{self, Write};
 ///
 /// let mut chaos = Chaos::new(stdout, options);
 /// ```
-pub struct Chaos<'a> {
+pub struct Chaos<'a, B: Backend = io::Stdout, E: EventSource = CrosstermEvents> {
     paddings: ChaosPaddings,
-    stdout: io::Stdout,
+    backend: B,
+    events: E,
     input_label: &'a str,
     dimensions: Vector2<u16>,
     position: Vector2<u16>,
+    history: VecDeque<String>,
+    history_capacity: usize,
+    alternate_screen_active: bool,
+    screen_buffer: Vec<Vec<Cell>>,
 }
 
-impl<'a> Chaos<'a> {
-    /// Instantiate the chaos engine with specified options.
+impl<'a, B: Backend> Chaos<'a, B, CrosstermEvents> {
+    /// Instantiate the chaos engine with the given backend and options, reading events from the
+    /// real terminal.
     ///
-    /// It enables raw mode where input must be handled manually.
-    pub fn new(stdout: io::Stdout, options: ChaosOptions<'a>) -> Self {
-        enable_raw_mode().unwrap();
+    /// When `backend` is a tty, this enables raw mode and bracketed paste, where input must be
+    /// handled manually. Non-tty backends are left untouched, since there's no terminal to put
+    /// into raw mode.
+    pub fn new(backend: B, options: ChaosOptions<'a>) -> Self {
+        Self::with_event_source(backend, CrosstermEvents, options)
+    }
+}
+
+impl<'a, B: Backend, E: EventSource> Chaos<'a, B, E> {
+    /// Instantiate the chaos engine with the given backend, event source, and options.
+    ///
+    /// This is the same as [`Chaos::new`], but lets a non-terminal [`EventSource`] (e.g.
+    /// [`ScriptedEvents`]) be plugged in, which is how `get_input`'s event loop is tested.
+    ///
+    /// When `backend` is a tty, this enables raw mode and bracketed paste, where input must be
+    /// handled manually. Non-tty backends are left untouched, since there's no terminal to put
+    /// into raw mode.
+    pub fn with_event_source(mut backend: B, events: E, options: ChaosOptions<'a>) -> Self {
+        if backend.is_tty() {
+            backend.enable_raw_mode().unwrap();
+            backend.enable_bracketed_paste().unwrap();
+        }
+
+        let dimensions = Self::initial_dimensions(&backend);
+        let position = Self::initial_position(&backend);
 
         Self {
-            stdout,
+            backend,
+            events,
             input_label: options.input_label,
-            dimensions: Self::get_dimensions(),
-            position: Self::get_position(),
+            dimensions,
+            position,
             paddings: ChaosPaddings {
                 input: Vector2::new(options.input_padding.x, options.input_padding.y),
                 buffer: Vector2::new(options.buffer_padding.x, options.buffer_padding.y),
             },
+            history: VecDeque::new(),
+            history_capacity: options.history_capacity,
+            alternate_screen_active: false,
+            screen_buffer: Self::blank_buffer(dimensions),
         }
     }
 
+    /// Builds a blank cell grid sized to `dimensions`, used as the initial back buffer.
+    fn blank_buffer(dimensions: Vector2<u16>) -> Vec<Vec<Cell>> {
+        vec![vec![Cell::default(); dimensions.x as usize]; dimensions.y as usize]
+    }
+
+    /// Returns the stored input history, most recent entry last.
+    pub fn history(&self) -> &VecDeque<String> {
+        &self.history
+    }
+
     /// Completely clears the terminal screen of any visible text.
     ///
     /// # Panics
     ///
-    /// Panics in the case of a terminal error.
+    /// Panics in the case of a backend error.
     pub fn clear_terminal(&mut self) {
-        self.stdout
-            .execute(terminal::Clear(terminal::ClearType::All))
-            .unwrap();
+        self.backend.clear().unwrap();
     }
 
     /// Returns the X position of the last character in the input.
-    fn last_character_pos(&self, input_len: usize) -> u16 {
-        // left padding + input label + input length
-        self.paddings.input.x + self.input_label.len() as u16 + 1 + input_len as u16
+    fn last_character_pos(&self, input_width: u16) -> u16 {
+        // left padding + input label + display width of the input so far
+        self.paddings.input.x + self.input_label.len() as u16 + 1 + input_width
+    }
+
+    /// Returns the display width of `input` up to (but not including) the byte index `caret`.
+    fn width_at(input: &str, caret: usize) -> u16 {
+        input[..caret].width() as u16
+    }
+
+    /// Returns whether a char of `char_width` columns still fits after `width_before` columns
+    /// of existing input, leaving at least one trailing column free.
+    fn fits_in_input(&self, width_before: u16, char_width: u16) -> bool {
+        self.last_character_pos(width_before) + char_width < self.dimensions.x
     }
 
     /// Gets input from the user.
@@ -78,7 +431,6 @@ impl<'a> Chaos<'a> {
     /// let mut chaos = Chaos::new(std::io::stdout(), ChaosOptions::default());
     ///
     /// loop {
-    ///     chaos.clear_terminal();
     ///     chaos.print(&mut page);
     ///
     ///     let input = chaos.get_input(&mut page).unwrap();
@@ -96,47 +448,180 @@ impl<'a> Chaos<'a> {
     /// This can panic when it fails to read the terminal events.
     pub fn get_input(&mut self, page: &mut Page) -> Result<String, io::Error> {
         let mut input = String::new();
+        let mut scratch = String::new();
+        let mut history_index: Option<usize> = None;
+        let mut caret: usize = 0;
         self.prepare_input();
 
         loop {
-            match event::read()? {
+            match self.events.read()? {
                 Event::Resize(_, _) => {
                     self.update_dimensions();
+                    self.screen_buffer = Self::blank_buffer(self.dimensions);
                     page.align(&self);
-                    self.clear_terminal();
                     self.print(page);
                     self.prepare_input();
 
-                    let last_character_pos = self.last_character_pos(input.len());
+                    let last_character_pos = self.last_character_pos(input.width() as u16);
 
                     if last_character_pos < self.dimensions.x {
-                        print!("{input}");
+                        write!(self.backend, "{input}").unwrap();
                         self.move_cursor(last_character_pos, self.dimensions.y - 1);
                         self.update_position();
                     } else {
                         input = String::new();
+                        caret = 0;
                     }
+
+                    caret = caret.min(input.len());
                 }
                 Event::Key(KeyEvent {
                     code: KeyCode::Backspace,
                     ..
-                }) if !input.is_empty() => {
-                    self.move_cursor(self.position.x - 1, self.position.y);
-                    print!(" ");
-                    self.move_cursor(self.position.x - 1, self.position.y);
+                }) if caret > 0 => {
+                    caret = Self::prev_char_boundary(&input, caret);
+                    input.remove(caret);
+                    self.redraw_tail(&input, caret, caret);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Delete,
+                    ..
+                }) if caret < input.len() => {
+                    input.remove(caret);
+                    self.redraw_tail(&input, caret, caret);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Left, ..
+                }) if caret > 0 => {
+                    caret = Self::prev_char_boundary(&input, caret);
+                    self.move_cursor(
+                        self.last_character_pos(Self::width_at(&input, caret)),
+                        self.dimensions.y - 1,
+                    );
+                    self.update_position();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Right,
+                    ..
+                }) if caret < input.len() => {
+                    caret = Self::next_char_boundary(&input, caret);
+                    self.move_cursor(
+                        self.last_character_pos(Self::width_at(&input, caret)),
+                        self.dimensions.y - 1,
+                    );
+                    self.update_position();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Home, ..
+                }) => {
+                    caret = 0;
+                    self.move_cursor(self.last_character_pos(0), self.dimensions.y - 1);
+                    self.update_position();
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::End, ..
+                }) => {
+                    caret = input.len();
+                    self.move_cursor(
+                        self.last_character_pos(Self::width_at(&input, caret)),
+                        self.dimensions.y - 1,
+                    );
                     self.update_position();
-                    input.pop();
                 }
                 Event::Key(KeyEvent {
                     code: KeyCode::Char(c),
                     ..
-                }) if c.is_ascii()
-                    && self.dimensions.x - 1 > self.last_character_pos(input.len()) =>
+                }) if self.fits_in_input(input.width() as u16, c.width().unwrap_or(1) as u16) =>
                 {
-                    print!("{c}");
-                    self.move_cursor(self.position.x + 1, self.position.y);
-                    self.update_position();
-                    input.push(c);
+                    let start = caret;
+                    input.insert(caret, c);
+                    caret += c.len_utf8();
+                    self.redraw_tail(&input, start, caret);
+                }
+                Event::Paste(data) => {
+                    let from = caret;
+                    // Pasted text lands in a single-line input buffer, so control characters
+                    // (newlines, carriage returns, tabs) must be dropped before splicing it in -
+                    // writing one to the backend would move the terminal cursor off the prompt.
+                    for c in data.chars().filter(|c| !c.is_control()) {
+                        let char_width = c.width().unwrap_or(1) as u16;
+                        if self.fits_in_input(input.width() as u16, char_width) {
+                            input.insert(caret, c);
+                            caret += c.len_utf8();
+                        }
+                    }
+                    self.redraw_tail(&input, from, caret);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Up, ..
+                }) if !self.history.is_empty() => {
+                    let new_index = match history_index {
+                        None => {
+                            scratch = input.clone();
+                            self.history.len() - 1
+                        }
+                        Some(0) => 0,
+                        Some(index) => index - 1,
+                    };
+
+                    history_index = Some(new_index);
+                    let old_width = input.width() as u16;
+                    input = self.history[new_index].clone();
+                    caret = input.len();
+                    self.redraw_input(old_width, &input);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Down,
+                    ..
+                }) if history_index.is_some() => {
+                    let index = history_index.unwrap();
+                    let old_width = input.width() as u16;
+
+                    if index + 1 < self.history.len() {
+                        history_index = Some(index + 1);
+                        input = self.history[index + 1].clone();
+                    } else {
+                        history_index = None;
+                        input = scratch.clone();
+                    }
+
+                    caret = input.len();
+                    self.redraw_input(old_width, &input);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Up, ..
+                }) if self.history.is_empty() => {
+                    page.scroll_by(-1);
+                    self.print(page);
+                    self.prepare_input();
+                    self.redraw_input(input.width() as u16, &input);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::Down,
+                    ..
+                }) if history_index.is_none() => {
+                    page.scroll_by(1);
+                    self.print(page);
+                    self.prepare_input();
+                    self.redraw_input(input.width() as u16, &input);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::PageUp,
+                    ..
+                }) => {
+                    page.scroll_by(-(self.dimensions.y.saturating_sub(1) as isize));
+                    self.print(page);
+                    self.prepare_input();
+                    self.redraw_input(input.width() as u16, &input);
+                }
+                Event::Key(KeyEvent {
+                    code: KeyCode::PageDown,
+                    ..
+                }) => {
+                    page.scroll_by(self.dimensions.y.saturating_sub(1) as isize);
+                    self.print(page);
+                    self.prepare_input();
+                    self.redraw_input(input.width() as u16, &input);
                 }
                 Event::Key(KeyEvent {
                     code: KeyCode::Enter,
@@ -146,13 +631,68 @@ impl<'a> Chaos<'a> {
             }
         }
 
+        if !input.is_empty() && self.history_capacity > 0 {
+            if self.history.len() >= self.history_capacity {
+                self.history.pop_front();
+            }
+            self.history.push_back(input.clone());
+        }
+
         Ok(input)
     }
 
+    /// Clears the currently displayed input line and reprints `new_input` in its place,
+    /// leaving the cursor at the end of it. `old_width` is the display width of what was
+    /// previously on the line.
+    fn redraw_input(&mut self, old_width: u16, new_input: &str) {
+        let start_x = self.paddings.input.x + self.input_label.len() as u16 + 1;
+        self.move_cursor(start_x, self.dimensions.y - 1);
+        write!(self.backend, "{}", " ".repeat(old_width as usize)).unwrap();
+        self.move_cursor(start_x, self.dimensions.y - 1);
+        write!(self.backend, "{new_input}").unwrap();
+        self.move_cursor(
+            self.last_character_pos(new_input.width() as u16),
+            self.dimensions.y - 1,
+        );
+        self.update_position();
+    }
+
+    /// Reprints `input` from the byte offset `from` to the end, plus a trailing space to
+    /// erase any character left over from a shorter edit, then moves the cursor to the column
+    /// of the byte offset `leave_at`.
+    fn redraw_tail(&mut self, input: &str, from: usize, leave_at: usize) {
+        self.move_cursor(
+            self.last_character_pos(Self::width_at(input, from)),
+            self.dimensions.y - 1,
+        );
+        write!(self.backend, "{} ", &input[from..]).unwrap();
+        self.move_cursor(
+            self.last_character_pos(Self::width_at(input, leave_at)),
+            self.dimensions.y - 1,
+        );
+        self.update_position();
+    }
+
+    /// Returns the byte index of the char boundary immediately before `caret`.
+    fn prev_char_boundary(input: &str, caret: usize) -> usize {
+        input[..caret]
+            .chars()
+            .next_back()
+            .map_or(0, |c| caret - c.len_utf8())
+    }
+
+    /// Returns the byte index of the char boundary immediately after `caret`.
+    fn next_char_boundary(input: &str, caret: usize) -> usize {
+        input[caret..]
+            .chars()
+            .next()
+            .map_or(input.len(), |c| caret + c.len_utf8())
+    }
+
     /// Prints the input prompt on the last line, and moves the cursor to the right position.
     fn prepare_input(&mut self) {
         self.move_cursor(self.paddings.input.x, self.dimensions.y - 1);
-        print!("{}", self.input_label);
+        write!(self.backend, "{}", self.input_label).unwrap();
         self.move_cursor(
             self.paddings.input.x + self.input_label.len() as u16 + 1,
             self.dimensions.y - 1,
@@ -164,10 +704,10 @@ impl<'a> Chaos<'a> {
     ///
     /// # Panics
     ///
-    /// Panics in the case of a terminal error.
+    /// Panics in the case of a backend error.
     pub fn move_cursor(&mut self, x: u16, y: u16) {
-        self.stdout.execute(cursor::MoveTo(x, y)).unwrap();
-        self.stdout.flush().unwrap();
+        self.backend.goto(x, y).unwrap();
+        self.backend.flush().unwrap();
     }
 
     /// Enables and disables the terminal's alternate screen.
@@ -178,33 +718,112 @@ impl<'a> Chaos<'a> {
     ///
     /// # Panics
     ///
-    /// Panics in the case of a terminal error.
+    /// Panics in the case of a backend error.
     pub fn alternate_screen(&mut self, on: bool) {
         if on {
-            self.stdout.execute(terminal::EnterAlternateScreen).unwrap();
+            self.backend.enter_alternate_screen().unwrap();
         } else {
-            self.stdout.execute(terminal::LeaveAlternateScreen).unwrap();
+            self.backend.leave_alternate_screen().unwrap();
         }
+
+        self.alternate_screen_active = on;
     }
 
     /// Prints the given `Page` onto the screen, respecting the paddings and word wrapping.
     ///
     /// Calls `Page::align()` on the given `Page` to apply the word wrapping before
-    /// printing it to the output.
+    /// printing it to the output. Only the cells that actually changed since the last
+    /// `print` are written, which keeps redraws flicker-free and proportional to the
+    /// amount of content that changed rather than the size of the screen. Styled spans are
+    /// rendered with their color/attributes when connected to a real terminal, and as plain
+    /// text otherwise. Only `page`'s viewport (starting at its scroll offset) is drawn, so
+    /// pages taller than the screen are scrollable rather than truncated.
     pub fn print(&mut self, page: &mut Page) {
-        let mut starting_line = self.paddings.buffer.y - 1;
-        self.move_cursor(starting_line, 0);
         page.align(&self);
 
-        for index in 0..page.text().len() {
-            let string = &page.text()[index];
-            if index >= self.dimensions.y as usize - 1 {
-                continue;
-            }
+        let width = self.dimensions.x as usize;
+        let height = self.dimensions.y as usize;
+        // text rows run from below the top padding down to the row above the input line
+        let first_row = (self.paddings.buffer.y as usize).max(1);
+        let visible_rows = height.saturating_sub(1).saturating_sub(first_row);
+
+        page.clamp_scroll(visible_rows);
+
+        if self.screen_buffer.len() != height || self.screen_buffer[0].len() != width {
+            self.screen_buffer = Self::blank_buffer(self.dimensions);
+        }
+
+        let mut front_buffer = Self::blank_buffer(self.dimensions);
+        let mut starting_line = (self.paddings.buffer.y as usize).saturating_sub(1);
+        let start_col = (self.paddings.buffer.x / 2) as usize;
+        let offset = page.scroll_offset();
+
+        for spans in page.text().iter().skip(offset).take(visible_rows) {
             starting_line += 1;
-            self.move_cursor(self.paddings.buffer.x / 2, starting_line);
-            print!("{string}");
+            if starting_line >= height {
+                break;
+            }
+
+            let mut col = start_col;
+            'line: for span in spans {
+                for c in span.text.chars() {
+                    if col >= width {
+                        break 'line;
+                    }
+                    front_buffer[starting_line][col] = Cell {
+                        ch: c,
+                        style: span.style,
+                    };
+                    col += c.width().unwrap_or(0).max(1);
+                }
+            }
         }
+
+        self.draw_diff(&front_buffer);
+        self.screen_buffer = front_buffer;
+    }
+
+    /// Writes only the cells that differ between `front_buffer` and the current back buffer,
+    /// coalescing runs of adjacent changed cells sharing the same style on the same row into a
+    /// single write.
+    fn draw_diff(&mut self, front_buffer: &[Vec<Cell>]) {
+        for (y, row) in front_buffer.iter().enumerate() {
+            let mut x = 0;
+
+            while x < row.len() {
+                if row[x] == self.screen_buffer[y][x] {
+                    x += 1;
+                    continue;
+                }
+
+                let start = x;
+                let style = row[x].style;
+                let mut run = String::new();
+
+                while x < row.len() && row[x] != self.screen_buffer[y][x] && row[x].style == style
+                {
+                    run.push(row[x].ch);
+                    x += 1;
+                }
+
+                self.move_cursor(start as u16, y as u16);
+                self.write_styled(&run, style);
+            }
+        }
+    }
+
+    /// Writes `text` with `style` applied, resetting the style afterwards. On a non-tty
+    /// backend, or for the default (unstyled) look, the text is written plain so redirected
+    /// output stays clean.
+    fn write_styled(&mut self, text: &str, style: Style) {
+        if !self.backend.is_tty() || style == Style::default() {
+            write!(self.backend, "{text}").unwrap();
+            return;
+        }
+
+        self.backend.set_style(style).unwrap();
+        write!(self.backend, "{text}").unwrap();
+        self.backend.reset_style().unwrap();
     }
 
     /// Returns the last stored position of the cursor.
@@ -212,15 +831,25 @@ impl<'a> Chaos<'a> {
         &self.position
     }
 
-    /// Returns the current cursor position.
-    fn get_position() -> Vector2<u16> {
-        let (pos_x, pos_y) = cursor::position().unwrap();
-        Vector2::new(pos_x, pos_y)
+    /// Returns the initial cursor position: the backend's actual cursor position when it's a
+    /// tty, or the origin otherwise (there's no real cursor to query).
+    fn initial_position(backend: &B) -> Vector2<u16> {
+        if backend.is_tty() {
+            let (pos_x, pos_y) = backend.cursor_position().unwrap();
+            Vector2::new(pos_x, pos_y)
+        } else {
+            Vector2::new(0, 0)
+        }
     }
 
     /// Updates the stored cursor position to the current one.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the case of a backend error.
     fn update_position(&mut self) {
-        self.position = Self::get_position();
+        let (pos_x, pos_y) = self.backend.cursor_position().unwrap();
+        self.position = Vector2::new(pos_x, pos_y);
     }
 
     /// Returns the last stored dimensions of the terminal.
@@ -228,19 +857,28 @@ impl<'a> Chaos<'a> {
         &self.dimensions
     }
 
-    /// Returns the current terminal dimensions.
+    /// Returns the initial dimensions: the backend's reported size, or [`DEFAULT_DIMENSIONS`] if
+    /// the backend can't report one (e.g. output redirected to a file or pipe).
+    fn initial_dimensions(backend: &B) -> Vector2<u16> {
+        match backend.size() {
+            Ok((dim_x, dim_y)) => Vector2::new(dim_x, dim_y),
+            Err(_) => Vector2::new(DEFAULT_DIMENSIONS.0, DEFAULT_DIMENSIONS.1),
+        }
+    }
+
+    /// Updates the stored dimensions of the terminal.
     ///
     /// # Panics
     ///
-    /// Panics in the case of a terminal error.
-    fn get_dimensions() -> Vector2<u16> {
-        let (dim_x, dim_y) = terminal::size().unwrap();
-        Vector2::new(dim_x, dim_y)
+    /// Panics in the case of a backend error.
+    fn update_dimensions(&mut self) {
+        let (dim_x, dim_y) = self.backend.size().unwrap();
+        self.dimensions = Vector2::new(dim_x, dim_y);
     }
 
-    /// Updates the stored dimensions of the terminal.
-    fn update_dimensions(&mut self) {
-        self.dimensions = Self::get_dimensions();
+    /// Returns a reference to the underlying backend.
+    pub fn backend(&self) -> &B {
+        &self.backend
     }
 
     /// Returns the current paddings.
@@ -257,6 +895,28 @@ impl<'a> Chaos<'a> {
     }
 }
 
+/// Restores the terminal to its original state when a [`Chaos`] instance is dropped.
+///
+/// On a tty backend, this leaves the alternate screen if it was entered, disables bracketed
+/// paste, shows the cursor, and disables raw mode, so a panic or an early return doesn't leave
+/// the user's terminal broken. Non-tty backends never have raw mode or bracketed paste enabled,
+/// so nothing is done for them, including leaving an alternate screen.
+impl<'a, B: Backend, E: EventSource> Drop for Chaos<'a, B, E> {
+    fn drop(&mut self) {
+        if !self.backend.is_tty() {
+            return;
+        }
+
+        if self.alternate_screen_active {
+            self.backend.leave_alternate_screen().ok();
+        }
+
+        self.backend.disable_bracketed_paste().ok();
+        self.backend.show_cursor().ok();
+        let _ = self.backend.disable_raw_mode();
+    }
+}
+
 /// A helper struct to set some options for a [`Chaos`] instance.
 ///
 /// # Examples
@@ -268,12 +928,14 @@ impl<'a> Chaos<'a> {
 ///     input_label: "Input:", // The input label
 ///     input_padding: Vector2::new(1, 1), // Input paddings (bottom line where input is written)
 ///     buffer_padding: Vector2::new(4, 2), // Buffer paddings (main text output area)
+///     history_capacity: 50, // Max number of submitted lines to keep for Up/Down recall
 /// };
 /// ```
 pub struct ChaosOptions<'a> {
     pub input_padding: Vector2<u16>,
     pub buffer_padding: Vector2<u16>,
     pub input_label: &'a str,
+    pub history_capacity: usize,
 }
 
 impl<'a> Default for ChaosOptions<'a> {
@@ -282,6 +944,7 @@ impl<'a> Default for ChaosOptions<'a> {
             input_label: "Input:",
             input_padding: Vector2::new(1, 0),
             buffer_padding: Vector2::new(8, 2),
+            history_capacity: 50,
         }
     }
 }
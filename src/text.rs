@@ -1,4 +1,40 @@
-use crate::Chaos;
+use crate::{Backend, Chaos, EventSource};
+use crossterm::style::Color;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// A run of text sharing a single [`Style`].
+///
+/// Lines pushed to a [`Page`] are made up of one or more spans; plain text pushed with
+/// [`Page::push`] is a single span with the default (unstyled) look.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+impl Span {
+    /// Creates a styled span from `text`.
+    pub fn new(text: &str, style: Style) -> Self {
+        Self {
+            text: text.to_string(),
+            style,
+        }
+    }
+
+    /// Creates a span with no styling applied.
+    pub fn plain(text: &str) -> Self {
+        Self::new(text, Style::default())
+    }
+}
+
+/// Foreground/background color and text attributes applied to a [`Span`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub foreground: Option<Color>,
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub underline: bool,
+}
 
 /// A page is a struct containing text to be printed by ChaosEngine. Pages are the building blocks
 /// for the programs made by the engine, they must be used to print text to the output.
@@ -13,8 +49,10 @@ use crate::Chaos;
 /// ```
 #[derive(Debug, PartialEq, Eq)]
 pub struct Page {
-    text: Vec<String>,
-    raw_text: Vec<String>,
+    text: Vec<Vec<Span>>,
+    raw_text: Vec<Vec<Span>>,
+    scroll_offset: usize,
+    auto_scroll: bool,
 }
 
 impl Page {
@@ -23,34 +61,143 @@ impl Page {
         Self {
             text: Vec::new(),
             raw_text: Vec::new(),
+            scroll_offset: 0,
+            auto_scroll: true,
         }
     }
 
     /// Push some string to the page. Each push will start on its own line.
+    ///
+    /// This also scrolls the page to the bottom, so newly pushed content is visible, unless
+    /// the user has since scrolled with [`Page::scroll_to`] or [`Page::scroll_by`].
     pub fn push(&mut self, text: &str) {
-        self.raw_text.push(text.to_string());
+        self.raw_text.push(vec![Span::plain(text)]);
+        self.auto_scroll = true;
+    }
+
+    /// Push a sequence of styled spans to the page. Each push will start on its own line, and
+    /// the styling of each span is preserved across word-wrapping in [`Page::align`].
+    ///
+    /// Like [`Page::push`], this scrolls the page to the bottom.
+    pub fn push_styled(&mut self, spans: Vec<Span>) {
+        self.raw_text.push(spans);
+        self.auto_scroll = true;
     }
 
     /// Pop the last string pushed to the page.
-    pub fn pop(&mut self) -> Option<String> {
+    pub fn pop(&mut self) -> Option<Vec<Span>> {
         self.raw_text.pop()
     }
 
-    /// Get the stored aligned text.
+    /// Returns the index of the first visible line in the aligned text.
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    /// Scrolls the page so that `line` is the first visible line, clamped to the page's
+    /// content once it is next printed. Disables auto-scroll-to-bottom until the next push.
+    pub fn scroll_to(&mut self, line: usize) {
+        self.scroll_offset = line;
+        self.auto_scroll = false;
+    }
+
+    /// Moves the scroll offset by `delta` lines (negative scrolls up, positive scrolls down),
+    /// clamped to the page's content once it is next printed. Disables auto-scroll-to-bottom
+    /// until the next push.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let offset = self.scroll_offset as isize + delta;
+        self.scroll_offset = offset.max(0) as usize;
+        self.auto_scroll = false;
+    }
+
+    /// Clamps the scroll offset to `[0, text.len() - visible_rows]`, snapping to the bottom if
+    /// auto-scroll is active. Called by [`crate::Chaos::print`] after aligning, since only it
+    /// knows how many rows are actually visible.
+    pub(crate) fn clamp_scroll(&mut self, visible_rows: usize) {
+        let max_offset = self.text.len().saturating_sub(visible_rows);
+
+        self.scroll_offset = if self.auto_scroll {
+            max_offset
+        } else {
+            self.scroll_offset.min(max_offset)
+        };
+    }
+
+    /// Get the stored aligned text, as lines of styled spans.
     ///
     /// Aligned text puts paddings and word-wrapping into consideration.
-    pub fn text(&self) -> &Vec<String> {
+    pub fn text(&self) -> &Vec<Vec<Span>> {
         &self.text
     }
 
-    /// Get the stored raw text.
-    pub fn raw_text(&self) -> &Vec<String> {
+    /// Get the stored raw text, as lines of styled spans.
+    pub fn raw_text(&self) -> &Vec<Vec<Span>> {
         &self.raw_text
     }
 
+    /// Appends `text` to `line`, merging it into the last span if that span already carries
+    /// the same `style`, so wrapping doesn't fragment a line into one span per character.
+    fn push_chunk(line: &mut Vec<Span>, text: &str, style: Style) {
+        if let Some(last) = line.last_mut() {
+            if last.style == style {
+                last.text.push_str(text);
+                return;
+            }
+        }
+        line.push(Span::new(text, style));
+    }
+
+    /// Splits `spans` into whitespace-separated words, tracking which span's style each
+    /// character came from. A word is represented as a sequence of same-style runs rather than
+    /// a single string, so a word that straddles a span boundary (e.g. only part of it is
+    /// highlighted) still wraps as one word instead of being torn in two at the boundary.
+    fn split_words(spans: &[Span]) -> Vec<Vec<(String, Style)>> {
+        let mut words: Vec<Vec<(String, Style)>> = Vec::new();
+        let mut current: Vec<(String, Style)> = Vec::new();
+
+        for span in spans {
+            for c in span.text.chars() {
+                if c.is_whitespace() {
+                    if !current.is_empty() {
+                        words.push(std::mem::take(&mut current));
+                    }
+                    continue;
+                }
+
+                if let Some(last) = current.last_mut() {
+                    if last.1 == span.style {
+                        last.0.push(c);
+                        continue;
+                    }
+                }
+                current.push((c.to_string(), span.style));
+            }
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+
+        words
+    }
+
+    /// Appends a word's style runs to `line`, followed by a trailing space carrying the style
+    /// of the word's last run.
+    fn push_word(line: &mut Vec<Span>, word: &[(String, Style)]) {
+        for (text, style) in word {
+            Self::push_chunk(line, text, *style);
+        }
+        if let Some((_, style)) = word.last() {
+            Self::push_chunk(line, " ", *style);
+        }
+    }
+
     /// Align the stored raw text, in other words, convert it to a properly formatted text,
     /// respecting paddings and word-wrapping.
-    pub fn align(&mut self, chaos: &Chaos) {
+    ///
+    /// Wrapping is measured in display columns rather than bytes, so wide (e.g. CJK) and
+    /// combining characters are handled correctly, and each word keeps the style of the span
+    /// it came from across line breaks.
+    pub fn align<B: Backend, E: EventSource>(&mut self, chaos: &Chaos<'_, B, E>) {
         if self.raw_text.is_empty() {
             return;
         }
@@ -59,34 +206,42 @@ impl Page {
         let dimensions = &chaos.dimensions();
         self.text = Vec::new();
 
-        for string in &self.raw_text {
-            let words: Vec<&str> = string.split_whitespace().collect();
-            let mut left_chars = dimensions.x as i32 - buffer_padding_x as i32;
-            let mut line = String::new();
-
-            for i in 0..words.len() {
-                let word = words[i];
-                let len = word.len() as i32;
-                if len > dimensions.x as i32 - buffer_padding_x as i32 {
-                    for c in word.chars() {
-                        if left_chars > 1 {
-                            line += &format!("{c}");
-                            left_chars -= 1;
-                        } else {
-                            line += &format!("{c}");
-                            self.text.push(line);
-                            line = String::new();
-                            left_chars = dimensions.x as i32 - buffer_padding_x as i32;
+        for spans in &self.raw_text {
+            let words = Self::split_words(spans);
+
+            let line_width = dimensions.x as i32 - buffer_padding_x as i32;
+            let mut left_chars = line_width;
+            let mut line: Vec<Span> = Vec::new();
+
+            for word in words {
+                let len: i32 = word.iter().map(|(text, _)| text.width() as i32).sum();
+                if len > line_width {
+                    for (text, style) in &word {
+                        for c in text.chars() {
+                            let char_width = c.width().unwrap_or(0) as i32;
+                            if left_chars < char_width {
+                                self.text.push(std::mem::take(&mut line));
+                                left_chars = line_width;
+                            }
+                            if left_chars > char_width {
+                                Self::push_chunk(&mut line, &c.to_string(), *style);
+                                left_chars -= char_width;
+                            } else {
+                                Self::push_chunk(&mut line, &c.to_string(), *style);
+                                self.text.push(line);
+                                line = Vec::new();
+                                left_chars = line_width;
+                            }
                         }
                     }
                 } else if left_chars > len {
-                    line += &format!("{word} ");
+                    Self::push_word(&mut line, &word);
                     left_chars -= len + 1;
                 } else {
                     self.text.push(line);
-                    line = String::new();
-                    line += &format!("{word} ");
-                    left_chars = dimensions.x as i32 - buffer_padding_x as i32 - len;
+                    line = Vec::new();
+                    Self::push_word(&mut line, &word);
+                    left_chars = line_width - len;
                 }
             }
             self.text.push(line);
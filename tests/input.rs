@@ -0,0 +1,87 @@
+mod tests {
+    use chaos_engine::{Chaos, ChaosOptions, MemoryBackend, Page, ScriptedEvents, types};
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+
+    /// Builds a `Chaos` over a blank `MemoryBackend`, fed the given scripted events in order,
+    /// with no paddings so column math in tests lines up with the raw column count.
+    fn input_chaos(
+        columns: u16,
+        rows: u16,
+        events: Vec<Event>,
+    ) -> Chaos<'static, MemoryBackend, ScriptedEvents> {
+        Chaos::with_event_source(
+            MemoryBackend::new(columns, rows),
+            ScriptedEvents::new(events),
+            ChaosOptions {
+                input_label: "",
+                input_padding: types::Vector2::new(0, 0),
+                buffer_padding: types::Vector2::new(0, 0),
+                ..ChaosOptions::default()
+            },
+        )
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn chars(s: &str) -> Vec<Event> {
+        s.chars().map(|c| key(KeyCode::Char(c))).collect()
+    }
+
+    fn enter() -> Event {
+        key(KeyCode::Enter)
+    }
+
+    #[test]
+    fn paste_strips_embedded_newlines() {
+        let mut chaos = input_chaos(
+            40,
+            5,
+            vec![Event::Paste("hello\nworld\r\n".to_string()), enter()],
+        );
+        let mut page = Page::new();
+
+        let input = chaos.get_input(&mut page).unwrap();
+
+        assert_eq!(input, "helloworld");
+    }
+
+    #[test]
+    fn history_up_down_recalls_previous_entries() {
+        let mut events = chars("a");
+        events.push(enter());
+        events.extend(chars("b"));
+        events.push(enter());
+        events.push(key(KeyCode::Up));
+        events.push(key(KeyCode::Up));
+        events.push(key(KeyCode::Down));
+        events.push(enter());
+
+        let mut chaos = input_chaos(40, 5, events);
+        let mut page = Page::new();
+
+        assert_eq!(chaos.get_input(&mut page).unwrap(), "a");
+        assert_eq!(chaos.get_input(&mut page).unwrap(), "b");
+        // Up recalls "b", a second Up walks back to "a", Down walks forward to "b" again.
+        assert_eq!(chaos.get_input(&mut page).unwrap(), "b");
+    }
+
+    #[test]
+    fn caret_editing_supports_mid_line_insert_and_navigation() {
+        let mut events = chars("ac");
+        events.push(key(KeyCode::Left));
+        events.push(key(KeyCode::Char('b')));
+        events.push(key(KeyCode::Home));
+        events.push(key(KeyCode::Char('<')));
+        events.push(key(KeyCode::End));
+        events.push(key(KeyCode::Char('>')));
+        events.push(enter());
+
+        let mut chaos = input_chaos(40, 5, events);
+        let mut page = Page::new();
+
+        // inserting "b" between "a" and "c", then "<"/">"  at the start/end of the line
+        assert_eq!(chaos.get_input(&mut page).unwrap(), "<abc>");
+    }
+}
@@ -1,13 +1,35 @@
-#[cfg(feature = "test")]
 mod tests {
-    use chaos_engine::ChaosTestOptions;
-    use chaos_engine::{Chaos, Page, types};
+    use chaos_engine::text::{Span, Style};
+    use chaos_engine::{Chaos, ChaosOptions, MemoryBackend, Page, types};
+    use crossterm::style::Color;
+
+    /// Builds a `Chaos` over a blank `MemoryBackend` of the given size, with no paddings so
+    /// width math in tests lines up with the raw column count.
+    fn test_chaos(columns: u16, rows: u16) -> Chaos<'static, MemoryBackend> {
+        Chaos::new(
+            MemoryBackend::new(columns, rows),
+            ChaosOptions {
+                input_label: "",
+                input_padding: types::Vector2::new(0, 0),
+                buffer_padding: types::Vector2::new(0, 0),
+                ..ChaosOptions::default()
+            },
+        )
+    }
+
+    /// Flattens a page's stored lines back down to plain strings for easy comparison.
+    fn plain(lines: &[Vec<Span>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|spans| spans.iter().map(|span| span.text.as_str()).collect())
+            .collect()
+    }
 
     #[test]
     fn instantiation() {
         let page = Page::new();
-        let text_should_expect: Vec<String> = Vec::new();
-        let raw_text_should_expect: Vec<String> = Vec::new();
+        let text_should_expect: Vec<Vec<Span>> = Vec::new();
+        let raw_text_should_expect: Vec<Vec<Span>> = Vec::new();
 
         assert_eq!(page.text(), &text_should_expect);
         assert_eq!(page.raw_text(), &raw_text_should_expect);
@@ -23,57 +45,118 @@ mod tests {
 
         // test pushing
         page.push(text0);
-        let mut expected = vec![text0];
+        let mut expected = vec![vec![Span::plain(text0)]];
         assert_eq!(page.raw_text(), &expected);
 
         // test pushing with one element already pushed
         page.push(text1);
         page.push(text2);
-        expected.push(text1);
-        expected.push(text2);
+        expected.push(vec![Span::plain(text1)]);
+        expected.push(vec![Span::plain(text2)]);
         assert_eq!(page.raw_text(), &expected);
 
         // test popping one element
         page.pop();
         expected.pop();
         assert_eq!(page.raw_text(), &expected);
-
-        // test clearing the page
-        page.clear();
-        assert_eq!(page.raw_text(), &Vec::<String>::new());
     }
 
     #[test]
     fn word_wrapping() {
-        let options = ChaosTestOptions {
-            stdout: std::io::stdout(),
-            input_label: "",
-            dimensions: types::Vector2::new(40, 40),
-            buffer_padding: types::Vector2::new(0, 0),
-            input_padding: types::Vector2::new(0, 0),
-            position: types::Vector2::new(0, 0),
-        };
-
-        let chaos = Chaos::test_setup(options);
+        let chaos = test_chaos(40, 40);
         let mut page = Page::new();
         page.push("This is a string that is enough to wrap into a new line.");
         page.align(&chaos);
 
         // string push should get wrapped onto a new line
-        assert_eq!(page.text()[0], "This is a string that is enough to wrap ");
-        assert_eq!(page.text()[1], "into a new line. ");
+        let lines = plain(page.text());
+        assert_eq!(lines[0], "This is a string that is enough to wrap ");
+        assert_eq!(lines[1], "into a new line. ");
 
         page.push("This is a string that shouldn't wrap.");
         page.align(&chaos);
 
         // string that fits on one line should use only one line
-        assert_eq!(page.text()[2], "This is a string that shouldn't wrap. ");
+        assert_eq!(plain(page.text())[2], "This is a string that shouldn't wrap. ");
 
         page.push("000000000000000000000000000000000000000000000000000000000000");
         page.align(&chaos);
 
         // single words that are longer than a line should soft-break
-        assert_eq!(page.text()[3], "0000000000000000000000000000000000000000");
-        assert_eq!(page.text()[4], "00000000000000000000");
+        let lines = plain(page.text());
+        assert_eq!(lines[3], "0000000000000000000000000000000000000000");
+        assert_eq!(lines[4], "00000000000000000000");
+    }
+
+    #[test]
+    fn styled_segments_preserve_style_across_wrapping() {
+        let chaos = test_chaos(20, 40);
+        let red = Style {
+            foreground: Some(Color::Red),
+            ..Style::default()
+        };
+
+        let mut page = Page::new();
+        page.push_styled(vec![
+            Span::new("short red text", red),
+            Span::plain("plain text that should wrap onto the next line"),
+        ]);
+        page.align(&chaos);
+
+        // the red words keep the red style, and the plain words the default style, on
+        // whichever wrapped line they land (same-style neighbours share a span)
+        let mut red_chars = String::new();
+        let mut plain_chars = String::new();
+        for line in page.text() {
+            for span in line {
+                let visible: String = span.text.split_whitespace().collect();
+                if span.style == red {
+                    red_chars.push_str(&visible);
+                } else {
+                    assert_eq!(span.style, Style::default());
+                    plain_chars.push_str(&visible);
+                }
+            }
+        }
+        assert_eq!(red_chars, "shortredtext");
+        assert_eq!(plain_chars, "plaintextthatshouldwrapontothenextline");
+    }
+
+    #[test]
+    fn scrolling_clamps_to_content_and_auto_scrolls_on_push() {
+        let mut chaos = test_chaos(40, 5);
+        let mut page = Page::new();
+        for i in 0..10 {
+            page.push(&format!("line {i}"));
+        }
+
+        // scrolling past the top clamps to 0
+        page.scroll_to(0);
+        page.scroll_by(-5);
+        chaos.print(&mut page);
+        assert_eq!(page.scroll_offset(), 0);
+
+        // pushing new content auto-scrolls back to the bottom
+        page.push("line 10");
+        chaos.print(&mut page);
+        assert!(page.scroll_offset() > 0);
+    }
+
+    #[test]
+    fn non_tty_backend_skips_styling() {
+        let mut chaos = test_chaos(20, 5);
+        let red = Style {
+            foreground: Some(Color::Red),
+            ..Style::default()
+        };
+
+        let mut page = Page::new();
+        page.push_styled(vec![Span::new("hello", red)]);
+        chaos.print(&mut page);
+
+        // MemoryBackend never reports as a tty, so styled output is written plain, with no
+        // ANSI escape sequences
+        assert!(chaos.backend().output().contains("hello"));
+        assert!(!chaos.backend().output().contains('\x1b'));
     }
 }